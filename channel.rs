@@ -0,0 +1,91 @@
+use crate::error::SwErlError;
+use crate::process::{Message, ProcessHandle};
+use crate::remote::RemoteHandle;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::marker::PhantomData;
+
+/// A request/reply carrier for a `Channel`: something that can send a typed
+/// request and get back a typed reply, whether that means pushing through an
+/// in-process mailbox (`ProcessHandle`) or CBOR-encoding it onto a socket
+/// (`RemoteHandle`). `Channel` only ever talks to this trait, which is what
+/// makes it location-transparent instead of merely claiming to be.
+#[async_trait::async_trait]
+pub trait Transport<T, U>: Send + Sync {
+    /// Sends `req` without waiting for a reply.
+    async fn cast(&self, req: T) -> Result<(), SwErlError>;
+    /// Sends `req` and waits for a typed reply.
+    async fn call(&self, req: T) -> Result<U, SwErlError>;
+}
+
+#[async_trait::async_trait]
+impl<T, U> Transport<T, U> for ProcessHandle
+where
+    T: Serialize + Send + Sync + 'static,
+    U: DeserializeOwned + Send + Sync + 'static,
+{
+    async fn cast(&self, req: T) -> Result<(), SwErlError> {
+        self.send(Box::new(req)).await
+    }
+
+    async fn call(&self, req: T) -> Result<U, SwErlError> {
+        let reply = ProcessHandle::call(self, Box::new(req)).await?;
+        downcast_reply(reply)
+    }
+}
+
+#[async_trait::async_trait]
+impl<T, U> Transport<T, U> for RemoteHandle<T, U>
+where
+    T: Serialize + Send + Sync + 'static,
+    U: DeserializeOwned + Send + Sync + 'static,
+{
+    async fn cast(&self, req: T) -> Result<(), SwErlError> {
+        RemoteHandle::cast(self, req).await
+    }
+
+    async fn call(&self, req: T) -> Result<U, SwErlError> {
+        RemoteHandle::call(self, req).await
+    }
+}
+
+fn downcast_reply<U: Send + Sync + 'static>(reply: Message) -> Result<U, SwErlError> {
+    reply
+        .downcast::<U>()
+        .map(|boxed| *boxed)
+        .map_err(|_| SwErlError::InvalidState("reply did not match the channel's reply type".to_string()))
+}
+
+/// A transport narrowed to a concrete, serializable request/reply pair.
+/// Generic over the `Transport` doing the carrying, so the exact same
+/// `Channel<T, U>` code talks to a local `ProcessHandle` (the default, and
+/// still the fast path) or a `RemoteHandle` on another node without the
+/// caller noticing which.
+#[derive(Clone)]
+pub struct Channel<T, U, X = ProcessHandle>
+where
+    X: Transport<T, U>,
+{
+    transport: X,
+    _request: PhantomData<fn(T)>,
+    _reply: PhantomData<fn() -> U>,
+}
+
+impl<T, U, X> Channel<T, U, X>
+where
+    X: Transport<T, U>,
+{
+    pub fn new(transport: X) -> Self {
+        Self { transport, _request: PhantomData, _reply: PhantomData }
+    }
+
+    /// Sends `req` without waiting for a reply.
+    pub async fn cast(&self, req: T) -> Result<(), SwErlError> {
+        self.transport.cast(req).await
+    }
+
+    /// Sends `req` and waits for a typed reply.
+    pub async fn call(&self, req: T) -> Result<U, SwErlError> {
+        self.transport.call(req).await
+    }
+}
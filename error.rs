@@ -1,3 +1,4 @@
+use std::sync::Arc;
 use thiserror::Error;
 
 /// Defines all possible errors in the RuErl runtime.
@@ -23,6 +24,21 @@ pub enum SwErlError {
     /// Error indicating an asynchronous 'cast' failed to send.
     #[error("Cast failed: {0}")]
     CastFailed(String),
+
+    /// Error indicating a supervisor exceeded its configured restart intensity
+    /// (more than `max_restarts` restarts within `max_seconds`) and gave up.
+    #[error("Restart intensity exceeded: {0}")]
+    RestartLimitExceeded(String),
+
+    /// Error indicating the process died from a non-recoverable error; carries
+    /// the original cause so every pending and future caller learns why,
+    /// rather than seeing a generic `MailboxClosed`.
+    #[error("Process closed: {0}")]
+    Closed(Arc<SwErlError>),
+
+    /// Error indicating a non-blocking send found the mailbox at capacity.
+    #[error("Process mailbox is overloaded")]
+    Overloaded,
 }
 
 impl SwErlError {
@@ -35,6 +51,9 @@ impl SwErlError {
             SwErlError::MailboxClosed => false,     // Not recoverable: process is dead
             SwErlError::Timeout => true,            // Recoverable: suggest retry
             SwErlError::CastFailed(_) => false,
+            SwErlError::RestartLimitExceeded(_) => false, // Not recoverable: supervisor has given up
+            SwErlError::Closed(_) => false,         // Not recoverable: process already died
+            SwErlError::Overloaded => true,         // Recoverable: caller can retry or shed load
         }
     }
 }
\ No newline at end of file
@@ -1,8 +1,10 @@
 use crate::error::SwErlError;
-use crate::process::{Message, State, ProcessBuilder, ProcessHandle};
+use crate::process::{Envelope, Message, State, ProcessBuilder, ProcessHandle};
+use crate::registry;
+use futures::future::BoxFuture;
 use std::sync::Arc;
-use tokio::sync::{Mutex, oneshot};
-use std::any::Any;
+use std::time::Duration;
+use tokio::sync::Mutex;
 
 /// The GenServer Behavior Protocol: defines the mandatory callbacks for an OTP server[cite: 322, 332].
 #[async_trait::async_trait]
@@ -15,12 +17,23 @@ pub trait GenServerBehavior: Send + Sync + 'static + Clone {
     
     /// Handles synchronous messages (call) and must return a reply[cite: 322, 332].
     async fn handle_call(&self, msg: Message, state: Arc<Mutex<State>>) -> Result<Message, SwErlError>;
+
+    /// Handles any message that's neither a cast nor a call, e.g. a timer
+    /// tick delivered directly via `ProcessHandle::send`. Default: ignore it.
+    async fn handle_info(&self, _msg: Message, _state: Arc<Mutex<State>>) -> Result<(), SwErlError> {
+        Ok(())
+    }
+
+    /// Called exactly once when the process's run loop ends, whether the
+    /// mailbox closed normally or a handler returned a non-recoverable error,
+    /// giving the behavior a chance to release resources. Default: no-op.
+    async fn terminate(&self, _reason: Result<(), SwErlError>, _state: Arc<Mutex<State>>) {}
 }
 
-// Internal wrapper structures for message differentiation
+// Internal wrapper structure for routing cast messages; calls are routed via
+// the shared `Envelope` type from `process`, so `ProcessHandle::call` and
+// `GenServer::call` agree on the wire format.
 struct CastMessage(Message);
-/// Wraps a call message and includes a oneshot channel sender for synchronous replies.
-struct CallMessage(Message, oneshot::Sender<Result<Message, SwErlError>>);
 
 pub struct GenServer;
 
@@ -31,42 +44,157 @@ impl GenServer {
         B: GenServerBehavior + Clone,
     {
         let initial_state = behavior.init(args).await?;
-        
-        let (_, handle) = ProcessBuilder::new().spawn(initial_state, move |state, msg| {
-            let behavior = behavior.clone();
-            async move {
-                // Runtime logic uses downcasting to semantically route the message (Cast vs Call)
-                if let Ok(CastMessage(inner_msg)) = msg.downcast::<CastMessage>() {
-                    behavior.handle_cast(inner_msg, state).await
-                } else if let Ok(CallMessage(inner_msg, reply_tx)) = msg.downcast::<CallMessage>() {
-                    let result = behavior.handle_call(inner_msg, state).await;
-                    // Send the result back to the caller immediately
-                    let _ = reply_tx.send(result);
-                    Ok(())
-                } else {
-                    // Ignore unknown message types
-                    Ok(())
-                }
-            }
+        let (_, handle, state, exit_rx) =
+            ProcessBuilder::new().spawn_monitored(initial_state, Self::dispatcher(behavior.clone()));
+
+        tokio::spawn(async move {
+            let reason = exit_rx.await.unwrap_or(Ok(()));
+            behavior.terminate(reason, state).await;
+        });
+
+        Ok(handle)
+    }
+
+    /// Starts a new GenServer process and registers it under `name`, so it
+    /// can later be addressed via `call_named`/`cast_named` instead of by PID.
+    /// The registration is removed once the process's run loop ends.
+    pub async fn start_named<B>(name: &str, behavior: B, args: Option<Message>) -> Result<ProcessHandle, SwErlError>
+    where
+        B: GenServerBehavior + Clone,
+    {
+        let initial_state = behavior.init(args).await?;
+        let (_, handle, state, exit_rx) = ProcessBuilder::new()
+            .name(name)
+            .spawn_monitored(initial_state, Self::dispatcher(behavior.clone()));
+
+        registry::global().register(name, handle.clone());
+        let name = name.to_string();
+        let pid = handle.pid.clone();
+        tokio::spawn(async move {
+            let reason = exit_rx.await.unwrap_or(Ok(()));
+            // Only remove the registration if it's still ours: a faster
+            // restart may already have claimed `name` for a new process.
+            registry::global().deregister(&name, &pid);
+            behavior.terminate(reason, state).await;
         });
-        
+
         Ok(handle)
     }
 
+    /// Builds the mailbox handler shared by `start` and `start_named`: it
+    /// downcasts each message to semantically route it as a cast or a call.
+    fn dispatcher<B>(behavior: B) -> impl FnMut(Arc<Mutex<State>>, Message) -> BoxFuture<'static, Result<(), SwErlError>>
+    where
+        B: GenServerBehavior + Clone,
+    {
+        move |state, msg| {
+            let behavior = behavior.clone();
+            Box::pin(async move {
+                match msg.downcast::<CastMessage>() {
+                    Ok(cast) => behavior.handle_cast(cast.0, state).await,
+                    Err(msg) => match msg.downcast::<Envelope>() {
+                        Ok(envelope) => {
+                            let Envelope(inner_msg, reply_tx) = *envelope;
+                            let result = behavior.handle_call(inner_msg, state).await;
+                            // Send the result back to the caller immediately
+                            let _ = reply_tx.send(result);
+                            Ok(())
+                        }
+                        // Neither a cast nor a call envelope: out-of-band message
+                        Err(msg) => behavior.handle_info(msg, state).await,
+                    },
+                }
+            })
+        }
+    }
+
     /// Sends an asynchronous message (cast) to the GenServer.
     pub async fn cast(handle: &ProcessHandle, msg: Message) -> Result<(), SwErlError> {
         handle.send(Box::new(CastMessage(msg))).await
     }
 
+    /// Resolves `name` through the global registry and sends it a cast.
+    pub async fn cast_named(name: &str, msg: Message) -> Result<(), SwErlError> {
+        let handle = registry::global().whereis(name)?;
+        Self::cast(&handle, msg).await
+    }
+
+    /// Like `cast`, but fails fast with `SwErlError::Overloaded` instead of
+    /// waiting for mailbox capacity.
+    pub fn try_cast(handle: &ProcessHandle, msg: Message) -> Result<(), SwErlError> {
+        handle.try_send(Box::new(CastMessage(msg)))
+    }
+
     /// Sends a synchronous message (call) and waits for a reply[cite: 322, 332].
+    /// Waits up to `process::DEFAULT_CALL_TIMEOUT`; use `call_timeout` to override.
     pub async fn call(handle: &ProcessHandle, msg: Message) -> Result<Message, SwErlError> {
-        let (tx, rx) = oneshot::channel();
-        handle.send(Box::new(CallMessage(msg, tx))).await?;
-        
-        // Block asynchronously, waiting for the server to process and reply
-        match rx.await {
-            Ok(res) => res,
-            Err(_) => Err(SwErlError::MailboxClosed), // Oneshot closed before reply
+        handle.call(msg).await
+    }
+
+    /// Resolves `name` through the global registry and sends it a call.
+    pub async fn call_named(name: &str, msg: Message) -> Result<Message, SwErlError> {
+        let handle = registry::global().whereis(name)?;
+        Self::call(&handle, msg).await
+    }
+
+    /// Like `call`, but fails with `SwErlError::Timeout` if the server doesn't
+    /// reply within `timeout`, mirroring actix's `Request`/`MailboxError` model.
+    pub async fn call_timeout(handle: &ProcessHandle, msg: Message, timeout: Duration) -> Result<Message, SwErlError> {
+        handle.call_timeout(msg, timeout).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex as StdMutex;
+    use std::time::Duration as StdDuration;
+
+    #[derive(Clone)]
+    struct RecordingBehavior {
+        log: Arc<StdMutex<Vec<&'static str>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl GenServerBehavior for RecordingBehavior {
+        async fn init(&self, _args: Option<Message>) -> Result<State, SwErlError> {
+            Ok(Box::new(()))
         }
+
+        async fn handle_cast(&self, _msg: Message, _state: Arc<Mutex<State>>) -> Result<(), SwErlError> {
+            Ok(())
+        }
+
+        async fn handle_call(&self, _msg: Message, _state: Arc<Mutex<State>>) -> Result<Message, SwErlError> {
+            Ok(Box::new(()))
+        }
+
+        async fn handle_info(&self, _msg: Message, _state: Arc<Mutex<State>>) -> Result<(), SwErlError> {
+            self.log.lock().unwrap().push("info");
+            Ok(())
+        }
+
+        async fn terminate(&self, _reason: Result<(), SwErlError>, _state: Arc<Mutex<State>>) {
+            self.log.lock().unwrap().push("terminate");
+        }
+    }
+
+    #[tokio::test]
+    async fn handle_info_routes_out_of_band_messages_and_terminate_runs_once_on_exit() {
+        let log = Arc::new(StdMutex::new(Vec::new()));
+        let behavior = RecordingBehavior { log: log.clone() };
+        let handle = GenServer::start(behavior, None).await.unwrap();
+
+        // A raw message downcasts to neither `CastMessage` nor `Envelope`, so
+        // the dispatcher must route it through `handle_info`.
+        handle.send(Box::new(42i32)).await.unwrap();
+        tokio::time::sleep(StdDuration::from_millis(20)).await;
+        assert_eq!(*log.lock().unwrap(), vec!["info"]);
+
+        // Dropping the last handle closes the mailbox, ending the run loop
+        // and firing `terminate` exactly once.
+        drop(handle);
+        tokio::time::sleep(StdDuration::from_millis(20)).await;
+        assert_eq!(*log.lock().unwrap(), vec!["info", "terminate"]);
     }
 }
\ No newline at end of file
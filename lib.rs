@@ -1,9 +1,17 @@
 pub mod error;
 pub mod process;
 pub mod gen_server;
+pub mod supervisor;
+pub mod registry;
+pub mod channel;
+pub mod remote;
 
 pub use error::SwErlError;
 pub use process::{Process, ProcessBuilder};
 pub use gen_server::{GenServer, GenServerBehavior};
+pub use supervisor::{ChildSpec, RestartPolicy, RestartStrategy, Supervisor, SupervisorHandle};
+pub use registry::Registry;
+pub use channel::{Channel, Transport};
+pub use remote::RemoteHandle;
 
 // Created using AALang and Gab.
\ No newline at end of file
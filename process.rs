@@ -1,103 +1,305 @@
-use crate::error::SwErlError;
-use std::any::Any;
-use std::sync::Arc;
-use tokio::sync::{mpsc, Mutex};
-use uuid::Uuid;
-
-pub type ProcessId = String;
-/// Messages are generic, dynamically typed, and thread-safe payloads.
-pub type Message = Box<dyn Any + Send + Sync>;
-/// State is generic, dynamic, and thread-safe (Arc<Mutex<...>> wraps this in the process struct).
-pub type State = Box<dyn Any + Send + Sync>;
-
-/// The internal handle for a process, holding its state and mailbox receiver.
-/// This represents the central 'Actor'.
-pub struct Process {
-    pub pid: ProcessId,
-    /// The process's internal state, modeled as AALang's 'Isolated Context'[cite: 14].
-    state: Arc<Mutex<State>>,
-    /// The process's asynchronous message queue, modeled as AALang's 'Shared Artifacts'[cite: 14].
-    mailbox: mpsc::Receiver<Message>,
-}
-
-impl Process {
-    pub fn new(pid: ProcessId, initial_state: State, mailbox: mpsc::Receiver<Message>) -> Self {
-        Self {
-            pid,
-            state: Arc::new(Mutex::new(initial_state)),
-            mailbox,
-        }
-    }
-
-    /// The core process run loop.
-    /// CRITICAL: Implements AALang's **Semantic Filtering** design principle[cite: 35].
-    /// It relies on asynchronous `recv().await` to suspend the task until a message
-    /// arrives, effectively avoiding explicit monitoring or polling of the mailbox.
-    pub async fn run<F, Fut>(mut self, mut handler: F) -> Result<(), SwErlError>
-    where
-        F: FnMut(Arc<Mutex<State>>, Message) -> Fut + Send + 'static,
-        Fut: std::future::Future<Output = Result<(), SwErlError>> + Send,
-    {
-        while let Some(msg) = self.mailbox.recv().await {
-            // The handler performs the semantic filtering based on message content
-            if let Err(e) = handler(self.state.clone(), msg).await {
-                eprintln!("Process {} error: {}", self.pid, e);
-                // Check if the error is recoverable before crashing/returning
-                if !e.is_recoverable() {
-                    return Err(e);
-                }
-            }
-        }
-        Ok(())
-    }
-}
-
-/// A handle used by other entities to send messages to this process[cite: 14, 13].
-#[derive(Clone)]
-pub struct ProcessHandle {
-    pub pid: ProcessId,
-    sender: mpsc::Sender<Message>,
-}
-
-impl ProcessHandle {
-    /// Sends a message asynchronously. Fails if the recipient's mailbox is closed.
-    pub async fn send(&self, msg: Message) -> Result<(), SwErlError> {
-        self.sender.send(msg).await.map_err(|_| SwErlError::MailboxClosed)
-    }
-}
-
-/// Builder pattern for creating and spawning new processes.
-pub struct ProcessBuilder {
-    name: Option<String>,
-}
-
-impl ProcessBuilder {
-    pub fn new() -> Self {
-        Self { name: None }
-    }
-
-    pub fn name(mut self, name: &str) -> Self {
-        self.name = Some(name.to_string());
-        self
-    }
-
-    /// Spawns the new process onto the Tokio runtime.
-    pub fn spawn<F, Fut>(self, initial_state: State, handler: F) -> (ProcessId, ProcessHandle)
-    where
-        F: FnMut(Arc<Mutex<State>>, Message) -> Fut + Send + 'static,
-        Fut: std::future::Future<Output = Result<(), SwErlError>> + Send,
-    {
-        let pid = self.name.unwrap_or_else(|| Uuid::new_v4().to_string());
-        let (tx, rx) = mpsc::channel(100); // Mailbox channel
-        
-        let process = Process::new(pid.clone(), initial_state, rx);
-        let handle = ProcessHandle { pid: pid.clone(), sender: tx };
-
-        // Launch the process asynchronously
-        tokio::spawn(async move {
-            let _ = process.run(handler).await;
-        });
-
-        (pid, handle)
-    }
-}
\ No newline at end of file
+use crate::error::SwErlError;
+use std::any::Any;
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot, Mutex};
+use uuid::Uuid;
+
+/// The default timeout for `ProcessHandle::call` / `GenServer::call`, mirroring
+/// actix's default `Request` timeout so a wedged process can't hang a caller forever.
+pub const DEFAULT_CALL_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub type ProcessId = String;
+/// Messages are generic, dynamically typed, and thread-safe payloads.
+pub type Message = Box<dyn Any + Send + Sync>;
+/// State is generic, dynamic, and thread-safe (Arc<Mutex<...>> wraps this in the process struct).
+pub type State = Box<dyn Any + Send + Sync>;
+
+/// The internal handle for a process, holding its state and mailbox receiver.
+/// This represents the central 'Actor'.
+pub struct Process {
+    pub pid: ProcessId,
+    /// The process's internal state, modeled as AALang's 'Isolated Context'[cite: 14].
+    state: Arc<Mutex<State>>,
+    /// The process's asynchronous message queue, modeled as AALang's 'Shared Artifacts'[cite: 14].
+    mailbox: mpsc::Receiver<Message>,
+    /// Notified with this process's exit reason when the run loop ends, so a
+    /// supervisor can detect death without relying on `eprintln!` alone.
+    exit_notifier: Option<oneshot::Sender<Result<(), SwErlError>>>,
+    /// Set once, when the process dies from a non-recoverable error. Shared
+    /// with every `ProcessHandle` clone so in-flight and future callers learn
+    /// the real cause instead of a generic `MailboxClosed`.
+    failure: Arc<StdMutex<Option<Arc<SwErlError>>>>,
+}
+
+impl Process {
+    pub fn new(pid: ProcessId, state: Arc<Mutex<State>>, mailbox: mpsc::Receiver<Message>) -> Self {
+        Self {
+            pid,
+            state,
+            mailbox,
+            exit_notifier: None,
+            failure: Arc::new(StdMutex::new(None)),
+        }
+    }
+
+    /// The core process run loop.
+    /// CRITICAL: Implements AALang's **Semantic Filtering** design principle[cite: 35].
+    /// It relies on asynchronous `recv().await` to suspend the task until a message
+    /// arrives, effectively avoiding explicit monitoring or polling of the mailbox.
+    pub async fn run<F, Fut>(mut self, mut handler: F) -> Result<(), SwErlError>
+    where
+        F: FnMut(Arc<Mutex<State>>, Message) -> Fut + Send + 'static,
+        Fut: std::future::Future<Output = Result<(), SwErlError>> + Send,
+    {
+        let exit_notifier = self.exit_notifier.take();
+        let result = self.run_loop(&mut handler).await;
+        if let Err(ref e) = result {
+            // Store the real cause so every ProcessHandle clone can report it,
+            // instead of callers only ever seeing a generic MailboxClosed.
+            *self.failure.lock().unwrap() = Some(Arc::new(e.clone()));
+        }
+        if let Some(notifier) = exit_notifier {
+            // Best-effort: a supervisor may have already stopped watching.
+            let _ = notifier.send(result.clone());
+        }
+        result
+    }
+
+    async fn run_loop<F, Fut>(&mut self, handler: &mut F) -> Result<(), SwErlError>
+    where
+        F: FnMut(Arc<Mutex<State>>, Message) -> Fut + Send + 'static,
+        Fut: std::future::Future<Output = Result<(), SwErlError>> + Send,
+    {
+        while let Some(msg) = self.mailbox.recv().await {
+            // The handler performs the semantic filtering based on message content
+            if let Err(e) = handler(self.state.clone(), msg).await {
+                eprintln!("Process {} error: {}", self.pid, e);
+                // Check if the error is recoverable before crashing/returning
+                if !e.is_recoverable() {
+                    return Err(e);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A handle used by other entities to send messages to this process[cite: 14, 13].
+#[derive(Clone)]
+pub struct ProcessHandle {
+    pub pid: ProcessId,
+    sender: mpsc::Sender<Message>,
+    failure: Arc<StdMutex<Option<Arc<SwErlError>>>>,
+    abort: tokio::task::AbortHandle,
+}
+
+/// Envelope for a synchronous round trip: carries the request plus a oneshot
+/// the process replies on. Shared by `ProcessHandle::call` and
+/// `GenServer::call`.
+pub(crate) struct Envelope(pub Message, pub oneshot::Sender<Result<Message, SwErlError>>);
+
+impl ProcessHandle {
+    /// Sends a message asynchronously. Fails if the recipient's mailbox is closed,
+    /// or if the process already died, in which case the stored cause is
+    /// returned wrapped in `SwErlError::Closed`.
+    pub async fn send(&self, msg: Message) -> Result<(), SwErlError> {
+        if let Some(cause) = self.failure_reason() {
+            return Err(SwErlError::Closed(cause));
+        }
+        self.sender.send(msg).await.map_err(|_| SwErlError::MailboxClosed)
+    }
+
+    /// Sends a message without waiting for mailbox capacity. Returns
+    /// `SwErlError::Overloaded` if the mailbox is full rather than applying
+    /// backpressure, so a caller can shed load instead of blocking.
+    pub fn try_send(&self, msg: Message) -> Result<(), SwErlError> {
+        if let Some(cause) = self.failure_reason() {
+            return Err(SwErlError::Closed(cause));
+        }
+        self.sender.try_send(msg).map_err(|e| match e {
+            mpsc::error::TrySendError::Full(_) => SwErlError::Overloaded,
+            mpsc::error::TrySendError::Closed(_) => SwErlError::MailboxClosed,
+        })
+    }
+
+    /// Sends `msg` wrapped in an `Envelope` and waits for the process to reply
+    /// on it; `GenServer`'s dispatcher answers these, and `GenServer::call`
+    /// delegates here. `Envelope` is crate-private, so a handler written
+    /// outside this crate cannot reply to it. Waits up to
+    /// `DEFAULT_CALL_TIMEOUT` before giving up.
+    pub async fn call(&self, msg: Message) -> Result<Message, SwErlError> {
+        self.call_timeout(msg, DEFAULT_CALL_TIMEOUT).await
+    }
+
+    /// Like `call`, but with an explicit timeout. If the process doesn't
+    /// reply in time, returns `SwErlError::Timeout` and abandons the reply
+    /// oneshot; a late reply is then silently dropped rather than panicking.
+    pub async fn call_timeout(&self, msg: Message, timeout: Duration) -> Result<Message, SwErlError> {
+        let (tx, rx) = oneshot::channel();
+        self.send(Box::new(Envelope(msg, tx))).await?;
+        match tokio::time::timeout(timeout, rx).await {
+            Ok(Ok(res)) => res,
+            // The oneshot closed without a reply, almost always because the
+            // process died mid-call; surface the real cause when we have it.
+            Ok(Err(_)) => Err(self.failure_reason().map(SwErlError::Closed).unwrap_or(SwErlError::MailboxClosed)),
+            Err(_) => Err(SwErlError::Timeout),
+        }
+    }
+
+    /// Returns the process's fatal error, if it has already died.
+    pub fn failure_reason(&self) -> Option<Arc<SwErlError>> {
+        self.failure.lock().unwrap().clone()
+    }
+
+    /// Forcibly stops the process's run loop. Used by `Supervisor` to tear
+    /// down still-running siblings before restarting them under
+    /// `OneForAll`/`RestForOne`, since those siblings haven't exited on their
+    /// own and there's nothing else watching their task.
+    pub fn kill(&self) {
+        self.abort.abort();
+    }
+}
+
+/// The mailbox capacity used when `ProcessBuilder::mailbox_capacity` isn't called.
+pub const DEFAULT_MAILBOX_CAPACITY: usize = 100;
+
+/// Return type of `ProcessBuilder::spawn_monitored`: the new process's id,
+/// handle, shared state, and a oneshot resolving with its exit reason.
+type MonitoredSpawn = (ProcessId, ProcessHandle, Arc<Mutex<State>>, oneshot::Receiver<Result<(), SwErlError>>);
+
+/// Return type of `ProcessBuilder::spawn_inner`: like `MonitoredSpawn`, but
+/// the exit receiver is only present when the caller asked to be monitored.
+type Spawn = (ProcessId, ProcessHandle, Arc<Mutex<State>>, Option<oneshot::Receiver<Result<(), SwErlError>>>);
+
+/// Builder pattern for creating and spawning new processes.
+pub struct ProcessBuilder {
+    name: Option<String>,
+    mailbox_capacity: usize,
+}
+
+impl Default for ProcessBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProcessBuilder {
+    pub fn new() -> Self {
+        Self { name: None, mailbox_capacity: DEFAULT_MAILBOX_CAPACITY }
+    }
+
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// Sets the mailbox's bounded capacity. Once full, `send` applies
+    /// backpressure by awaiting space, while `try_send` fails fast with
+    /// `SwErlError::Overloaded`.
+    pub fn mailbox_capacity(mut self, capacity: usize) -> Self {
+        self.mailbox_capacity = capacity;
+        self
+    }
+
+    /// Spawns the new process onto the Tokio runtime.
+    pub fn spawn<F, Fut>(self, initial_state: State, handler: F) -> (ProcessId, ProcessHandle)
+    where
+        F: FnMut(Arc<Mutex<State>>, Message) -> Fut + Send + 'static,
+        Fut: std::future::Future<Output = Result<(), SwErlError>> + Send,
+    {
+        let (pid, handle, _, _) = self.spawn_inner(initial_state, handler, false);
+        (pid, handle)
+    }
+
+    /// Spawns the new process, also returning its shared state and a oneshot
+    /// that resolves with the process's exit reason once its run loop ends.
+    /// Used by `Supervisor` to detect child death without polling, and by
+    /// `GenServer` to invoke `terminate` with the final state.
+    pub fn spawn_monitored<F, Fut>(self, initial_state: State, handler: F) -> MonitoredSpawn
+    where
+        F: FnMut(Arc<Mutex<State>>, Message) -> Fut + Send + 'static,
+        Fut: std::future::Future<Output = Result<(), SwErlError>> + Send,
+    {
+        let (pid, handle, state, exit_rx) = self.spawn_inner(initial_state, handler, true);
+        (pid, handle, state, exit_rx.expect("spawn_inner(monitored=true) always returns a receiver"))
+    }
+
+    fn spawn_inner<F, Fut>(self, initial_state: State, handler: F, monitored: bool) -> Spawn
+    where
+        F: FnMut(Arc<Mutex<State>>, Message) -> Fut + Send + 'static,
+        Fut: std::future::Future<Output = Result<(), SwErlError>> + Send,
+    {
+        let pid = self.name.unwrap_or_else(|| Uuid::new_v4().to_string());
+        let (tx, rx) = mpsc::channel(self.mailbox_capacity); // Mailbox channel
+        let state = Arc::new(Mutex::new(initial_state));
+
+        let mut process = Process::new(pid.clone(), state.clone(), rx);
+        let exit_rx = if monitored {
+            let (exit_tx, exit_rx) = oneshot::channel();
+            process.exit_notifier = Some(exit_tx);
+            Some(exit_rx)
+        } else {
+            None
+        };
+        let failure = process.failure.clone();
+
+        // Launch the process asynchronously
+        let task = tokio::spawn(async move {
+            let _ = process.run(handler).await;
+        });
+        let handle = ProcessHandle { pid: pid.clone(), sender: tx, failure, abort: task.abort_handle() };
+
+        (pid, handle, state, exit_rx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration as StdDuration;
+
+    #[tokio::test]
+    async fn failure_reason_is_returned_to_callers_after_a_crash() {
+        let (_, handle) = ProcessBuilder::new()
+            .spawn(Box::new(()), |_state, _msg| async move { Err(SwErlError::InvalidState("boom".to_string())) });
+
+        // Crash the process, then give its run loop a moment to store the cause.
+        handle.send(Box::new(())).await.unwrap();
+        tokio::time::sleep(StdDuration::from_millis(20)).await;
+
+        match handle.send(Box::new(())).await {
+            Err(SwErlError::Closed(cause)) => assert!(matches!(*cause, SwErlError::InvalidState(_))),
+            other => panic!("expected Closed(InvalidState), got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn call_timeout_errors_out_instead_of_hanging_forever() {
+        let (_, handle) = ProcessBuilder::new().spawn(Box::new(()), |_state, msg| async move {
+            // Hold onto the message (and the reply oneshot inside it) for as
+            // long as this future is alive, instead of replying to it, to
+            // simulate a wedged handler.
+            std::future::pending::<()>().await;
+            drop(msg);
+            Ok(())
+        });
+
+        let result = handle.call_timeout(Box::new(()), StdDuration::from_millis(20)).await;
+        let err = result.err();
+        assert!(matches!(err, Some(SwErlError::Timeout)), "expected Timeout, got {:?}", err);
+    }
+
+    #[tokio::test]
+    async fn try_send_fails_fast_when_the_mailbox_is_full() {
+        let (_, handle) = ProcessBuilder::new().mailbox_capacity(1).spawn(Box::new(()), |_state, msg| async move {
+            // Never drains the mailbox, so the one slot fills up immediately.
+            std::future::pending::<()>().await;
+            drop(msg);
+            Ok(())
+        });
+
+        handle.try_send(Box::new(())).unwrap();
+        assert!(matches!(handle.try_send(Box::new(())), Err(SwErlError::Overloaded)));
+    }
+}
@@ -0,0 +1,78 @@
+use crate::error::SwErlError;
+use crate::process::{ProcessHandle, ProcessId};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// A name -> `ProcessHandle` directory, the "PostOffice" layer that lets one
+/// process address another purely by logical name instead of a PID.
+#[derive(Clone, Default)]
+pub struct Registry {
+    processes: Arc<Mutex<HashMap<String, ProcessHandle>>>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `handle` under `name`, replacing any previous registration.
+    pub fn register(&self, name: &str, handle: ProcessHandle) {
+        self.processes.lock().unwrap().insert(name.to_string(), handle);
+    }
+
+    /// Removes `name`'s registration, but only if it still points at `pid`.
+    /// Called once a named process's run loop ends, so a dead name doesn't
+    /// keep resolving to a stale handle — conditional on identity so a dying
+    /// process's deferred cleanup can't clobber a newer process that's
+    /// already claimed the same name (e.g. a `Supervisor` restart).
+    pub fn deregister(&self, name: &str, pid: &ProcessId) {
+        let mut processes = self.processes.lock().unwrap();
+        if processes.get(name).is_some_and(|handle| handle.pid == *pid) {
+            processes.remove(name);
+        }
+    }
+
+    /// Looks up a process by name.
+    pub fn whereis(&self, name: &str) -> Result<ProcessHandle, SwErlError> {
+        self.processes
+            .lock()
+            .unwrap()
+            .get(name)
+            .cloned()
+            .ok_or_else(|| SwErlError::ProcessNotFound(name.to_string()))
+    }
+}
+
+static GLOBAL: OnceLock<Registry> = OnceLock::new();
+
+/// Returns the process-wide registry, creating it on first use.
+pub fn global() -> &'static Registry {
+    GLOBAL.get_or_init(Registry::new)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::process::ProcessBuilder;
+
+    #[tokio::test]
+    async fn deregister_does_not_clobber_a_name_reused_by_a_newer_process() {
+        let registry = Registry::new();
+        let (_, old_handle) = ProcessBuilder::new().spawn(Box::new(()), |_state, _msg| async move { Ok(()) });
+        registry.register("worker", old_handle.clone());
+
+        // A replacement claims the same name before the old process's
+        // deferred cleanup gets around to running.
+        let (_, new_handle) = ProcessBuilder::new().spawn(Box::new(()), |_state, _msg| async move { Ok(()) });
+        registry.register("worker", new_handle.clone());
+
+        // The old process's cleanup fires last, but must leave the new
+        // registration alone since it no longer points at its own pid.
+        registry.deregister("worker", &old_handle.pid);
+        assert_eq!(registry.whereis("worker").unwrap().pid, new_handle.pid);
+
+        // Once the current holder's own cleanup runs, the entry is removed.
+        registry.deregister("worker", &new_handle.pid);
+        assert!(registry.whereis("worker").is_err());
+    }
+}
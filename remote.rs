@@ -0,0 +1,231 @@
+use crate::error::SwErlError;
+use crate::process::{Message, ProcessHandle};
+use futures::{SinkExt, StreamExt};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex as StdMutex};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::sync::oneshot;
+use tokio_util::codec::{FramedRead, FramedWrite, LengthDelimitedCodec};
+
+/// One frame on the wire: a cast (no reply expected), a call (awaits a reply
+/// carrying the same correlation id), or a reply to an earlier call. The
+/// request/reply payloads themselves are CBOR-encoded separately so this
+/// envelope doesn't need to know their concrete type.
+#[derive(Serialize, Deserialize)]
+enum Frame {
+    Cast { payload: Vec<u8> },
+    Call { id: u64, payload: Vec<u8> },
+    Reply { id: u64, payload: Result<Vec<u8>, String> },
+}
+
+type Writer = Arc<tokio::sync::Mutex<FramedWrite<Box<dyn AsyncWrite + Send + Unpin>, LengthDelimitedCodec>>>;
+
+/// A location-transparent handle that offers the same `cast`/`call` surface
+/// as `ProcessHandle`, but carries messages as length-prefixed CBOR frames
+/// over a Tokio stream instead of an in-process `mpsc` channel. Pair with
+/// `serve` on the other end so a `GenServer` can be addressed identically
+/// whether it lives in this process or on another node.
+pub struct RemoteHandle<T, U> {
+    writer: Writer,
+    pending: PendingReplies<U>,
+    next_id: AtomicU64,
+    _request: PhantomData<fn(T)>,
+}
+
+/// Calls awaiting a reply, keyed by correlation id.
+type PendingReplies<U> = Arc<StdMutex<HashMap<u64, oneshot::Sender<Result<U, SwErlError>>>>>;
+
+impl<T, U> RemoteHandle<T, U>
+where
+    T: Serialize + Send + Sync + 'static,
+    U: DeserializeOwned + Send + Sync + 'static,
+{
+    /// Wraps an already-connected stream (e.g. a `TcpStream`). Spawns a
+    /// background task that reads replies off the stream and routes each one
+    /// back to the pending `call` with the matching correlation id.
+    pub fn new<S>(stream: S) -> Self
+    where
+        S: AsyncRead + AsyncWrite + Send + Unpin + 'static,
+    {
+        let (read_half, write_half) = tokio::io::split(stream);
+        let reader = FramedRead::new(read_half, LengthDelimitedCodec::new());
+        let write_half: Box<dyn AsyncWrite + Send + Unpin> = Box::new(write_half);
+        let writer = Arc::new(tokio::sync::Mutex::new(FramedWrite::new(write_half, LengthDelimitedCodec::new())));
+        let pending: PendingReplies<U> = Arc::new(StdMutex::new(HashMap::new()));
+
+        tokio::spawn(Self::read_replies(reader, pending.clone()));
+
+        Self { writer, pending, next_id: AtomicU64::new(0), _request: PhantomData }
+    }
+
+    async fn read_replies(
+        mut reader: FramedRead<impl AsyncRead + Unpin, LengthDelimitedCodec>,
+        pending: PendingReplies<U>,
+    ) {
+        while let Some(Ok(bytes)) = reader.next().await {
+            let Ok(Frame::Reply { id, payload }) = ciborium::de::from_reader(&bytes[..]) else {
+                continue;
+            };
+            if let Some(tx) = pending.lock().unwrap().remove(&id) {
+                let result = payload
+                    .map_err(SwErlError::CastFailed)
+                    .and_then(|bytes| {
+                        ciborium::de::from_reader::<U, _>(&bytes[..]).map_err(|e| SwErlError::InvalidState(e.to_string()))
+                    });
+                let _ = tx.send(result);
+            }
+        }
+        // The connection closed: fail every call still waiting on a reply
+        // instead of leaving its oneshot dangling, so `RemoteHandle::call`
+        // returns `MailboxClosed` rather than hanging forever.
+        for (_, tx) in pending.lock().unwrap().drain() {
+            let _ = tx.send(Err(SwErlError::MailboxClosed));
+        }
+    }
+
+    /// Sends `req` without waiting for a reply.
+    pub async fn cast(&self, req: T) -> Result<(), SwErlError> {
+        let payload = encode(&req)?;
+        self.write_frame(Frame::Cast { payload }).await
+    }
+
+    /// Sends `req` and waits for the remote process's typed reply.
+    pub async fn call(&self, req: T) -> Result<U, SwErlError> {
+        let payload = encode(&req)?;
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (tx, rx) = oneshot::channel();
+        self.pending.lock().unwrap().insert(id, tx);
+
+        if let Err(e) = self.write_frame(Frame::Call { id, payload }).await {
+            self.pending.lock().unwrap().remove(&id);
+            return Err(e);
+        }
+
+        rx.await.unwrap_or(Err(SwErlError::MailboxClosed))
+    }
+
+    async fn write_frame(&self, frame: Frame) -> Result<(), SwErlError> {
+        let mut bytes = Vec::new();
+        ciborium::ser::into_writer(&frame, &mut bytes).map_err(|e| SwErlError::CastFailed(e.to_string()))?;
+        self.writer.lock().await.send(bytes.into()).await.map_err(|_| SwErlError::MailboxClosed)
+    }
+}
+
+fn encode<T: Serialize>(value: &T) -> Result<Vec<u8>, SwErlError> {
+    let mut bytes = Vec::new();
+    ciborium::ser::into_writer(value, &mut bytes).map_err(|e| SwErlError::CastFailed(e.to_string()))?;
+    Ok(bytes)
+}
+
+/// Bridges a remote connection to a local process: decodes incoming CBOR
+/// frames as `T`, forwards them to `handle` as ordinary local messages, and
+/// CBOR-encodes `U` replies back onto the stream. Runs until the connection
+/// closes.
+pub async fn serve<S, T, U>(stream: S, handle: ProcessHandle)
+where
+    S: AsyncRead + AsyncWrite + Send + Unpin + 'static,
+    T: DeserializeOwned + Send + Sync + 'static,
+    U: Serialize + Send + Sync + 'static,
+{
+    let (read_half, write_half) = tokio::io::split(stream);
+    let mut reader = FramedRead::new(read_half, LengthDelimitedCodec::new());
+    let writer: Writer = Arc::new(tokio::sync::Mutex::new(FramedWrite::new(
+        Box::new(write_half) as Box<dyn AsyncWrite + Send + Unpin>,
+        LengthDelimitedCodec::new(),
+    )));
+
+    while let Some(Ok(bytes)) = reader.next().await {
+        let Ok(frame) = ciborium::de::from_reader::<Frame, _>(&bytes[..]) else {
+            continue;
+        };
+        match frame {
+            Frame::Cast { payload } => {
+                if let Ok(req) = ciborium::de::from_reader::<T, _>(&payload[..]) {
+                    let _ = handle.send(Box::new(req)).await;
+                }
+            }
+            Frame::Call { id, payload } => {
+                tokio::spawn(handle_call::<T, U>(payload, id, handle.clone(), writer.clone()));
+            }
+            Frame::Reply { .. } => {} // Only meaningful on the RemoteHandle side.
+        }
+    }
+}
+
+async fn handle_call<T, U>(payload: Vec<u8>, id: u64, handle: ProcessHandle, writer: Writer)
+where
+    T: DeserializeOwned + Send + Sync + 'static,
+    U: Serialize + Send + Sync + 'static,
+{
+    let reply: Result<Vec<u8>, String> = async {
+        let req: T = ciborium::de::from_reader(&payload[..]).map_err(|e| e.to_string())?;
+        let reply: Message = handle.call(Box::new(req)).await.map_err(|e| e.to_string())?;
+        let reply: U = *reply
+            .downcast::<U>()
+            .map_err(|_| "reply did not match the expected remote reply type".to_string())?;
+        encode(&reply).map_err(|e| e.to_string())
+    }
+    .await;
+
+    let frame = Frame::Reply { id, payload: reply };
+    let mut bytes = Vec::new();
+    if ciborium::ser::into_writer(&frame, &mut bytes).is_ok() {
+        let _ = writer.lock().await.send(bytes.into()).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gen_server::{GenServer, GenServerBehavior};
+    use std::time::Duration as StdDuration;
+    use tokio::sync::Mutex as TokioMutex;
+
+    #[derive(Clone)]
+    struct Doubler;
+
+    #[async_trait::async_trait]
+    impl GenServerBehavior for Doubler {
+        async fn init(&self, _args: Option<Message>) -> Result<crate::process::State, SwErlError> {
+            Ok(Box::new(()))
+        }
+
+        async fn handle_cast(&self, _msg: Message, _state: Arc<TokioMutex<crate::process::State>>) -> Result<(), SwErlError> {
+            Ok(())
+        }
+
+        async fn handle_call(&self, msg: Message, _state: Arc<TokioMutex<crate::process::State>>) -> Result<Message, SwErlError> {
+            let n = *msg.downcast::<i32>().map_err(|_| SwErlError::InvalidState("expected i32".to_string()))?;
+            Ok(Box::new(n * 2))
+        }
+    }
+
+    #[tokio::test]
+    async fn call_round_trips_over_a_cbor_framed_stream() {
+        let handle = GenServer::start(Doubler, None).await.unwrap();
+        let (client_io, server_io) = tokio::io::duplex(4096);
+        tokio::spawn(serve::<_, i32, i32>(server_io, handle));
+
+        let remote: RemoteHandle<i32, i32> = RemoteHandle::new(client_io);
+        assert_eq!(remote.call(21).await.unwrap(), 42);
+    }
+
+    #[tokio::test]
+    async fn pending_call_resolves_with_an_error_when_the_connection_drops() {
+        let (client_io, server_io) = tokio::io::duplex(4096);
+        let remote: RemoteHandle<i32, i32> = RemoteHandle::new(client_io);
+
+        let call = tokio::spawn(async move { remote.call(1).await });
+        // Give the call a moment to register its pending reply before
+        // severing the connection out from under it.
+        tokio::time::sleep(StdDuration::from_millis(20)).await;
+        drop(server_io);
+
+        let err = call.await.unwrap().unwrap_err();
+        assert!(matches!(err, SwErlError::MailboxClosed));
+    }
+}
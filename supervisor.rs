@@ -0,0 +1,323 @@
+use crate::error::SwErlError;
+use crate::process::{ProcessHandle, ProcessId};
+use futures::future::select_all;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+use tokio::sync::oneshot;
+use uuid::Uuid;
+
+/// Determines whether a terminated child should be restarted, mirroring OTP's
+/// `child_spec` restart types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestartPolicy {
+    /// Always restart the child, regardless of exit reason.
+    Permanent,
+    /// Restart only if the child exited with a non-recoverable error.
+    Transient,
+    /// Never restart the child.
+    Temporary,
+}
+
+/// Determines which siblings are affected when one child terminates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestartStrategy {
+    /// Restart only the child that died.
+    OneForOne,
+    /// Kill and restart every child whenever any one of them dies.
+    OneForAll,
+    /// Restart the dead child and every child started after it.
+    RestForOne,
+}
+
+/// A closure that (re)starts a child, returning its handle and an exit
+/// notifier the supervisor watches to detect death (see
+/// `ProcessBuilder::spawn_monitored`).
+type Start = Box<dyn Fn() -> (ProcessHandle, oneshot::Receiver<Result<(), SwErlError>>) + Send + Sync>;
+
+/// A specification describing how to start and supervise one child process.
+pub struct ChildSpec {
+    id: String,
+    restart: RestartPolicy,
+    start: Start,
+}
+
+impl ChildSpec {
+    /// Creates a child spec. `start` is invoked once at supervisor boot and
+    /// again every time the child needs to be restarted.
+    pub fn new<F>(id: &str, restart: RestartPolicy, start: F) -> Self
+    where
+        F: Fn() -> (ProcessHandle, oneshot::Receiver<Result<(), SwErlError>>) + Send + Sync + 'static,
+    {
+        Self { id: id.to_string(), restart, start: Box::new(start) }
+    }
+}
+
+struct Child {
+    spec: ChildSpec,
+    handle: ProcessHandle,
+    exit_rx: oneshot::Receiver<Result<(), SwErlError>>,
+}
+
+/// Supervises a set of child processes, restarting them according to an OTP
+/// restart strategy and intensity limit.
+pub struct Supervisor {
+    strategy: RestartStrategy,
+    max_restarts: usize,
+    max_seconds: u64,
+    children: Vec<ChildSpec>,
+}
+
+/// A handle to a running supervisor. The supervisor's own exit reason (e.g.
+/// `SwErlError::RestartLimitExceeded`) can be awaited via `wait`.
+pub struct SupervisorHandle {
+    pub pid: ProcessId,
+    exit_rx: oneshot::Receiver<SwErlError>,
+}
+
+impl SupervisorHandle {
+    /// Waits for the supervisor to give up and terminate, returning the error
+    /// that caused it to do so.
+    pub async fn wait(self) -> Result<SwErlError, SwErlError> {
+        self.exit_rx.await.map_err(|_| SwErlError::MailboxClosed)
+    }
+}
+
+impl Supervisor {
+    pub fn new(strategy: RestartStrategy, max_restarts: usize, max_seconds: u64) -> Self {
+        Self { strategy, max_restarts, max_seconds, children: Vec::new() }
+    }
+
+    /// Adds a child spec, started in the order added.
+    pub fn child(mut self, spec: ChildSpec) -> Self {
+        self.children.push(spec);
+        self
+    }
+
+    /// Starts every child and spawns the supervision loop onto the Tokio
+    /// runtime.
+    pub fn start(self) -> SupervisorHandle {
+        let pid = Uuid::new_v4().to_string();
+        let (exit_tx, exit_rx) = oneshot::channel();
+        let sup_pid = pid.clone();
+
+        tokio::spawn(async move {
+            if let Err(e) = Self::supervise(&sup_pid, self.strategy, self.max_restarts, self.max_seconds, self.children).await {
+                eprintln!("Supervisor {} terminating: {}", sup_pid, e);
+                let _ = exit_tx.send(e);
+            }
+        });
+
+        SupervisorHandle { pid, exit_rx }
+    }
+
+    async fn supervise(
+        pid: &str,
+        strategy: RestartStrategy,
+        max_restarts: usize,
+        max_seconds: u64,
+        specs: Vec<ChildSpec>,
+    ) -> Result<(), SwErlError> {
+        let mut children: Vec<Child> = specs
+            .into_iter()
+            .map(|spec| {
+                let (handle, exit_rx) = (spec.start)();
+                Child { spec, handle, exit_rx }
+            })
+            .collect();
+
+        // Ring buffer of recent restart timestamps, used to enforce the
+        // max_restarts-within-max_seconds intensity limit.
+        let mut restarts: VecDeque<Instant> = VecDeque::with_capacity(max_restarts + 1);
+
+        loop {
+            let (index, reason) = match Self::wait_for_exit(&mut children).await {
+                Some(result) => result,
+                None => return Ok(()), // No children left to supervise.
+            };
+
+            eprintln!("Supervisor {}: child {} exited: {:?}", pid, children[index].spec.id, reason);
+
+            if !Self::should_restart(children[index].spec.restart, &reason) {
+                children.remove(index);
+                if children.is_empty() {
+                    return Ok(());
+                }
+                continue;
+            }
+
+            let now = Instant::now();
+            restarts.push_back(now);
+            while let Some(&oldest) = restarts.front() {
+                if now.duration_since(oldest) > Duration::from_secs(max_seconds) {
+                    restarts.pop_front();
+                } else {
+                    break;
+                }
+            }
+            if restarts.len() > max_restarts {
+                return Err(SwErlError::RestartLimitExceeded(format!(
+                    "more than {} restarts within {}s",
+                    max_restarts, max_seconds
+                )));
+            }
+
+            match strategy {
+                RestartStrategy::OneForOne => Self::restart_child(&mut children, index),
+                RestartStrategy::OneForAll => {
+                    for i in 0..children.len() {
+                        Self::restart_child(&mut children, i);
+                    }
+                }
+                RestartStrategy::RestForOne => {
+                    for i in index..children.len() {
+                        Self::restart_child(&mut children, i);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Races every child's exit notifier, returning the index and exit
+    /// reason of whichever one resolves first.
+    async fn wait_for_exit(children: &mut [Child]) -> Option<(usize, Result<(), SwErlError>)> {
+        if children.is_empty() {
+            return None;
+        }
+        let waiters = children.iter_mut().map(|c| &mut c.exit_rx);
+        let (result, index, _) = select_all(waiters).await;
+        Some((index, result.unwrap_or(Err(SwErlError::MailboxClosed))))
+    }
+
+    fn should_restart(policy: RestartPolicy, reason: &Result<(), SwErlError>) -> bool {
+        match policy {
+            RestartPolicy::Permanent => true,
+            RestartPolicy::Temporary => false,
+            RestartPolicy::Transient => matches!(reason, Err(e) if !e.is_recoverable()),
+        }
+    }
+
+    /// Stops the child's current run loop (a no-op if it already died on its
+    /// own, necessary if it's a still-running sibling being torn down for
+    /// `OneForAll`/`RestForOne`) and starts a fresh one in its place.
+    fn restart_child(children: &mut [Child], index: usize) {
+        children[index].handle.kill();
+        let (handle, exit_rx) = (children[index].spec.start)();
+        children[index].handle = handle;
+        children[index].exit_rx = exit_rx;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::process::ProcessBuilder;
+    use std::sync::{Arc, Mutex as StdMutex};
+    use std::time::Duration as StdDuration;
+
+    /// A `ChildSpec` whose process crashes with a non-recoverable error the
+    /// moment it receives any message. Every handle `start` hands out is
+    /// recorded in `handles`, so a test can grab the latest one to crash it
+    /// and inspect earlier ones to check they were actually killed.
+    fn crashing_child(id: &'static str, handles: Arc<StdMutex<Vec<ProcessHandle>>>) -> ChildSpec {
+        ChildSpec::new(id, RestartPolicy::Permanent, move || {
+            let handles = handles.clone();
+            let (_, handle, _, exit_rx) = ProcessBuilder::new()
+                .spawn_monitored(Box::new(()), |_state, _msg| async move { Err(SwErlError::InvalidState("boom".to_string())) });
+            handles.lock().unwrap().push(handle.clone());
+            (handle, exit_rx)
+        })
+    }
+
+    /// A `ChildSpec` whose process never crashes on its own. Used as a
+    /// sibling to observe what a restart strategy does to processes that
+    /// didn't fail themselves.
+    fn steady_child(id: &'static str, handles: Arc<StdMutex<Vec<ProcessHandle>>>) -> ChildSpec {
+        ChildSpec::new(id, RestartPolicy::Permanent, move || {
+            let handles = handles.clone();
+            let (_, handle, _, exit_rx) =
+                ProcessBuilder::new().spawn_monitored(Box::new(()), |_state, _msg| async move { Ok(()) });
+            handles.lock().unwrap().push(handle.clone());
+            (handle, exit_rx)
+        })
+    }
+
+    #[tokio::test]
+    async fn one_for_one_restarts_only_the_dead_child() {
+        let handles: Arc<StdMutex<Vec<ProcessHandle>>> = Arc::new(StdMutex::new(Vec::new()));
+        let _sup = Supervisor::new(RestartStrategy::OneForOne, 5, 10)
+            .child(crashing_child("crasher", handles.clone()))
+            .start();
+
+        tokio::time::sleep(StdDuration::from_millis(30)).await;
+        let first = handles.lock().unwrap()[0].clone();
+        first.send(Box::new(())).await.unwrap();
+
+        tokio::time::sleep(StdDuration::from_millis(50)).await;
+        assert_eq!(handles.lock().unwrap().len(), 2, "the crashed child should have been restarted exactly once");
+    }
+
+    #[tokio::test]
+    async fn one_for_all_kills_and_restarts_every_sibling() {
+        let crasher_handles: Arc<StdMutex<Vec<ProcessHandle>>> = Arc::new(StdMutex::new(Vec::new()));
+        let sibling_handles: Arc<StdMutex<Vec<ProcessHandle>>> = Arc::new(StdMutex::new(Vec::new()));
+        let _sup = Supervisor::new(RestartStrategy::OneForAll, 5, 10)
+            .child(crashing_child("crasher", crasher_handles.clone()))
+            .child(steady_child("sibling", sibling_handles.clone()))
+            .start();
+
+        tokio::time::sleep(StdDuration::from_millis(30)).await;
+        let crasher = crasher_handles.lock().unwrap()[0].clone();
+        let sibling_gen0 = sibling_handles.lock().unwrap()[0].clone();
+        crasher.send(Box::new(())).await.unwrap();
+
+        tokio::time::sleep(StdDuration::from_millis(50)).await;
+        assert_eq!(
+            sibling_handles.lock().unwrap().len(),
+            2,
+            "the undamaged sibling should have been restarted too under OneForAll"
+        );
+        // The old sibling's run loop must actually have been killed, not left
+        // running alongside its replacement.
+        assert!(sibling_gen0.send(Box::new(())).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn rest_for_one_leaves_earlier_siblings_alone() {
+        let before_handles: Arc<StdMutex<Vec<ProcessHandle>>> = Arc::new(StdMutex::new(Vec::new()));
+        let crasher_handles: Arc<StdMutex<Vec<ProcessHandle>>> = Arc::new(StdMutex::new(Vec::new()));
+        let after_handles: Arc<StdMutex<Vec<ProcessHandle>>> = Arc::new(StdMutex::new(Vec::new()));
+        let _sup = Supervisor::new(RestartStrategy::RestForOne, 5, 10)
+            .child(steady_child("before", before_handles.clone()))
+            .child(crashing_child("crasher", crasher_handles.clone()))
+            .child(steady_child("after", after_handles.clone()))
+            .start();
+
+        tokio::time::sleep(StdDuration::from_millis(30)).await;
+        let before_gen0 = before_handles.lock().unwrap()[0].clone();
+        let crasher = crasher_handles.lock().unwrap()[0].clone();
+        crasher.send(Box::new(())).await.unwrap();
+
+        tokio::time::sleep(StdDuration::from_millis(50)).await;
+        assert_eq!(before_handles.lock().unwrap().len(), 1, "children started before the dead one must be left alone");
+        assert!(before_gen0.send(Box::new(())).await.is_ok(), "the untouched sibling should still be alive");
+        assert_eq!(crasher_handles.lock().unwrap().len(), 2, "the dead child should be restarted");
+        assert_eq!(after_handles.lock().unwrap().len(), 2, "children started after the dead one should be restarted too");
+    }
+
+    #[tokio::test]
+    async fn exceeding_restart_intensity_terminates_the_supervisor() {
+        let handles: Arc<StdMutex<Vec<ProcessHandle>>> = Arc::new(StdMutex::new(Vec::new()));
+        let sup = Supervisor::new(RestartStrategy::OneForOne, 1, 60).child(crashing_child("crasher", handles.clone()));
+        let sup_handle = sup.start();
+
+        tokio::time::sleep(StdDuration::from_millis(30)).await;
+        for _ in 0..3 {
+            let latest = handles.lock().unwrap().last().unwrap().clone();
+            let _ = latest.send(Box::new(())).await;
+            tokio::time::sleep(StdDuration::from_millis(30)).await;
+        }
+
+        let result = sup_handle.wait().await;
+        assert!(matches!(result, Ok(SwErlError::RestartLimitExceeded(_))));
+    }
+}